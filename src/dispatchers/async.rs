@@ -1,51 +1,74 @@
 use std::{
 	any::{Any, TypeId},
+	cmp::Reverse,
+	collections::HashMap,
 	future::Future,
+	hash::Hash,
 	pin::Pin,
-	sync::Arc,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
 };
-use tokio::sync::RwLock;
+use tokio::sync::{mpsc, Mutex, RwLock};
 
-use crate::Event;
+use crate::{ControlFlow, Event, ListenerId};
 
 type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + Sync + 'a>>;
 
 pub trait EventHandler<Ev: Event + 'static>: Send + Sync {
-	fn handle(&self, event: Ev) -> BoxFuture<'_, ()>;
+	fn handle(&self, event: Ev) -> BoxFuture<'_, ControlFlow>;
 }
 
 impl<Ev: Event + Send + Sync + 'static, F, Fut> EventHandler<Ev> for F
 where
 	F: Fn(Ev) -> Fut + Send + Sync,
-	Fut: Future<Output = ()> + Send + Sync + 'static,
+	Fut: Future<Output = ControlFlow> + Send + Sync + 'static,
 {
-	fn handle(&self, event: Ev) -> BoxFuture<'_, ()> {
-		Box::pin(async move {
-			(self)(event).await;
-		})
+	fn handle(&self, event: Ev) -> BoxFuture<'_, ControlFlow> {
+		Box::pin(async move { (self)(event).await })
 	}
 }
 
 #[allow(clippy::type_complexity)]
 struct AsyncListener {
-	event: TypeId,
-	handler: Box<dyn (Fn(&dyn Any) -> BoxFuture<'_, ()>) + Send + Sync>,
+	id: ListenerId,
+	priority: i32,
+	handler: Box<dyn (Fn(&(dyn Any + Send + Sync)) -> BoxFuture<'_, ControlFlow>) + Send + Sync>,
 }
 
-pub struct Dispatcher {
-	listeners: RwLock<Vec<AsyncListener>>,
+/// The latest coalesced value for a high-frequency event type, as queued by
+/// [`Dispatcher::notify`] and drained by [`Dispatcher::flush`].
+struct PendingEvent {
+	pending: AtomicBool,
+	latest: Mutex<Option<Box<dyn Any + Send + Sync>>>,
 }
 
-impl Dispatcher {
+/// An event dispatcher, optionally scoped by a `Topic` key (`()` by default, which preserves
+/// plain type-based routing).
+#[allow(clippy::type_complexity)]
+pub struct Dispatcher<Topic = ()> {
+	listeners: RwLock<HashMap<(TypeId, Option<Topic>), Vec<AsyncListener>>>,
+	next_id: u64,
+	pending: RwLock<HashMap<TypeId, PendingEvent>>,
+}
+
+impl Dispatcher<()> {
 	/// Create a new dispatcher.
 	#[must_use]
 	pub fn new() -> Self {
-		Self {
-			listeners: RwLock::new(Vec::new()),
-		}
+		Self::default()
 	}
+}
 
-	/// Register an event listener with the dispatcher.
+impl<Topic: Eq + Hash + Send + Sync> Dispatcher<Topic> {
+	/// Register an event listener with the dispatcher, returning a [`ListenerId`] that can later
+	/// be passed to [`Dispatcher::forget`] to remove it.
+	///
+	/// Listeners are invoked in descending order of `priority` (ties keep registration order).
+	/// Returning [`ControlFlow::Stop`] from the handler halts propagation to lower-priority
+	/// listeners for that dispatch. Only [`Dispatcher::dispatch`] and [`Dispatcher::flush`] (not
+	/// [`Dispatcher::dispatch_to`]) ever call a listener registered this way.
 	///
 	/// # Panics
 	///
@@ -55,59 +78,247 @@ impl Dispatcher {
 		Handler: EventHandler<Ev> + 'static,
 	>(
 		&mut self,
+		priority: i32,
+		on_event: Handler,
+	) -> ListenerId {
+		self.register::<Ev, Handler>(None, priority, on_event).await
+	}
+
+	/// Register an event listener scoped to a `topic`, returning a [`ListenerId`] that can later
+	/// be passed to [`Dispatcher::forget`] to remove it.
+	///
+	/// Only [`Dispatcher::dispatch_to`] calls with a matching `topic` reach listeners registered
+	/// this way; plain [`Dispatcher::dispatch`] never does.
+	///
+	/// # Panics
+	///
+	/// This function will panic if the event type does not match the dispatcher's event type.
+	pub async fn listen_on<
+		Ev: Event + Clone + Send + Sync + 'static,
+		Handler: EventHandler<Ev> + 'static,
+	>(
+		&mut self,
+		topic: Topic,
+		priority: i32,
+		on_event: Handler,
+	) -> ListenerId {
+		self.register::<Ev, Handler>(Some(topic), priority, on_event)
+			.await
+	}
+
+	async fn register<
+		Ev: Event + Clone + Send + Sync + 'static,
+		Handler: EventHandler<Ev> + 'static,
+	>(
+		&mut self,
+		topic: Option<Topic>,
+		priority: i32,
 		on_event: Handler,
-	) {
+	) -> ListenerId {
 		let on_event = Arc::new(on_event);
 		let mut listeners = self.listeners.write().await;
 
-		listeners.push(AsyncListener {
-			event: TypeId::of::<Ev>(),
-			handler: Box::new(move |ev: &dyn Any| {
-				let ev = ev
-					.downcast_ref::<Ev>()
-					.expect("Event type mismatch in dispatcher")
-					.clone();
-				let on_event = on_event.clone();
-
-				Box::pin(async move { on_event.handle(ev).await })
-			}),
-		});
+		let id = ListenerId(self.next_id);
+		self.next_id += 1;
+
+		listeners
+			.entry((TypeId::of::<Ev>(), topic))
+			.or_default()
+			.push(AsyncListener {
+				id,
+				priority,
+				handler: Box::new(move |ev: &(dyn Any + Send + Sync)| {
+					let ev = ev
+						.downcast_ref::<Ev>()
+						.expect("Event type mismatch in dispatcher")
+						.clone();
+					let on_event = on_event.clone();
+
+					Box::pin(async move { on_event.handle(ev).await })
+				}),
+			});
+
+		id
+	}
+
+	/// Subscribe to an event type without providing a callback.
+	///
+	/// Instead of invoking a handler, dispatched events are cloned onto the returned channel, which
+	/// holds up to `buffer` pending events, for the caller to `recv()` from directly in a loop or a
+	/// `select!`. While the channel is full, a matching dispatch waits until the receiver makes
+	/// room. The returned [`ListenerId`] is the forwarding listener registered to do this;
+	/// once the receiver is dropped, pass it to [`Dispatcher::forget`] to remove the now-inert
+	/// listener. Dropping the `ListenerId` instead of `forget`-ing it leaks that listener for the
+	/// dispatcher's lifetime — it no longer sends anywhere, but [`Dispatcher::has_listeners`] keeps
+	/// reporting it and it still runs (as a no-op) on every matching dispatch.
+	pub async fn subscribe<Ev: Event + Clone + Send + Sync + 'static>(
+		&mut self,
+		buffer: usize,
+	) -> (ListenerId, mpsc::Receiver<Ev>) {
+		let (tx, rx) = mpsc::channel(buffer);
+
+		let id = self
+			.listen(0, move |event: Ev| {
+				let tx = tx.clone();
+				async move {
+					let _ = tx.send(event).await;
+					ControlFlow::Continue
+				}
+			})
+			.await;
+
+		(id, rx)
+	}
+
+	/// Remove a previously registered listener, in any topic.
+	pub async fn forget(&mut self, id: ListenerId) {
+		self.listeners
+			.write()
+			.await
+			.values_mut()
+			.for_each(|bucket| bucket.retain(|listener| listener.id != id));
+	}
+
+	/// Remove every listener registered for a given event type, across all topics.
+	pub async fn forget_all<Ev: Event + 'static>(&mut self) {
+		self.listeners
+			.write()
+			.await
+			.retain(|(event, _), _| *event != TypeId::of::<Ev>());
 	}
 
-	/// Determine if a given event has listeners.
+	/// Determine if a given event has listeners, in any topic.
 	pub async fn has_listeners<Ev: Event + 'static>(&self) -> bool {
 		let listeners = self.listeners.read().await;
 
-		listeners.iter().any(|l| l.event == TypeId::of::<Ev>())
+		listeners
+			.iter()
+			.any(|(key, bucket)| key.0 == TypeId::of::<Ev>() && !bucket.is_empty())
 	}
 
-	/// Fire an event and call the listeners.
+	/// Fire an event and call the type-only listeners registered via [`Dispatcher::listen`],
+	/// highest priority first.
+	///
+	/// Returns [`ControlFlow::Stop`] if a listener halted propagation, or
+	/// [`ControlFlow::Continue`] once every matching listener has run.
 	///
 	/// # Errors
 	///
 	/// Returns an error if the event type is not registered with the dispatcher.
-	#[allow(clippy::significant_drop_in_scrutinee)]
 	pub async fn dispatch<Ev: Event + Send + Sync + 'static>(
 		&self,
 		event: &Ev,
-	) -> Result<(), Error> {
+	) -> Result<ControlFlow, Error> {
+		self.dispatch_matching(None, event).await
+	}
+
+	/// Fire an event for a specific `topic`, calling only listeners registered for that
+	/// `(event, topic)` pair via [`Dispatcher::listen_on`], highest priority first.
+	///
+	/// Returns [`ControlFlow::Stop`] if a listener halted propagation, or
+	/// [`ControlFlow::Continue`] once every matching listener has run.
+	///
+	/// # Errors
+	///
+	/// Never returns an error; the result is always `Ok`.
+	pub async fn dispatch_to<Ev: Event + Send + Sync + 'static>(
+		&self,
+		topic: &Topic,
+		event: &Ev,
+	) -> Result<ControlFlow, Error>
+	where
+		Topic: Clone,
+	{
+		self.dispatch_matching(Some(topic.clone()), event).await
+	}
+
+	async fn dispatch_matching<Ev: Event + Send + Sync + 'static>(
+		&self,
+		topic: Option<Topic>,
+		event: &Ev,
+	) -> Result<ControlFlow, Error> {
+		Ok(self.dispatch_key(&(TypeId::of::<Ev>(), topic), event).await)
+	}
+
+	/// Queue the latest value of a high-frequency event, coalescing it with any value of the same
+	/// type that [`Dispatcher::flush`] hasn't delivered yet, instead of fanning out immediately.
+	/// Only the type-only listeners registered via [`Dispatcher::listen`] ever see a coalesced
+	/// event; topics play no part here.
+	#[allow(clippy::significant_drop_tightening)]
+	pub async fn notify<Ev: Event + Send + Sync + 'static>(&self, event: Ev) {
+		let type_id = TypeId::of::<Ev>();
+
+		if let Some(entry) = self.pending.read().await.get(&type_id) {
+			*entry.latest.lock().await = Some(Box::new(event));
+			entry.pending.store(true, Ordering::Release);
+			return;
+		}
+
+		let mut pending = self.pending.write().await;
+		let entry = pending.entry(type_id).or_insert_with(|| PendingEvent {
+			pending: AtomicBool::new(false),
+			latest: Mutex::new(None),
+		});
+		*entry.latest.lock().await = Some(Box::new(event));
+		entry.pending.store(true, Ordering::Release);
+	}
+
+	/// Deliver at most one coalesced event per type to the type-only listeners registered via
+	/// [`Dispatcher::listen`], for every type [`Dispatcher::notify`] queued since the last flush.
+	pub async fn flush(&self) {
+		let mut due = Vec::new();
+
+		{
+			let pending = self.pending.read().await;
+
+			for (&type_id, entry) in pending.iter() {
+				if !entry.pending.swap(false, Ordering::AcqRel) {
+					continue;
+				}
+
+				if let Some(event) = entry.latest.lock().await.take() {
+					due.push((type_id, event));
+				}
+			}
+		}
+
+		for (type_id, event) in due {
+			self.dispatch_key(&(type_id, None), &*event).await;
+		}
+	}
+
+	#[allow(clippy::significant_drop_in_scrutinee, clippy::significant_drop_tightening)]
+	async fn dispatch_key(
+		&self,
+		key: &(TypeId, Option<Topic>),
+		event: &(dyn Any + Send + Sync),
+	) -> ControlFlow {
 		let listeners = self.listeners.read().await;
 
-		let futures = listeners
-			.iter()
-			.filter(|listener| listener.event == TypeId::of::<Ev>())
-			.map(|listener| (listener.handler)(event));
+		let Some(bucket) = listeners.get(key) else {
+			return ControlFlow::Continue;
+		};
+
+		let mut matching = bucket.iter().collect::<Vec<_>>();
+		matching.sort_by_key(|listener| Reverse(listener.priority));
 
-		futures::future::join_all(futures).await;
-		drop(listeners);
+		for listener in matching {
+			if (listener.handler)(event).await == ControlFlow::Stop {
+				return ControlFlow::Stop;
+			}
+		}
 
-		Ok(())
+		ControlFlow::Continue
 	}
 }
 
-impl Default for Dispatcher {
+impl<Topic> Default for Dispatcher<Topic> {
 	fn default() -> Self {
-		Self::new()
+		Self {
+			listeners: RwLock::new(HashMap::new()),
+			next_id: 0,
+			pending: RwLock::new(HashMap::new()),
+		}
 	}
 }
 
@@ -133,7 +344,124 @@ mod tests {
 		let mut dispatcher = Dispatcher::new();
 
 		dispatcher
-			.listen(|event: OrderShipped| async move { assert_eq!(event.order_id, 123) })
+			.listen(0, |event: OrderShipped| async move {
+				assert_eq!(event.order_id, 123);
+				ControlFlow::Continue
+			})
+			.await;
+
+		dispatcher
+			.dispatch(&OrderShipped { order_id: 123 })
+			.await
+			.unwrap();
+	}
+
+	#[tokio::test]
+	async fn test_halting_propagation() {
+		let mut dispatcher = Dispatcher::new();
+
+		dispatcher
+			.listen(10, |_: OrderShipped| async move { ControlFlow::Stop })
+			.await;
+		dispatcher
+			.listen(0, |_: OrderShipped| async move {
+				panic!("lower-priority listener should not run after a halt");
+			})
+			.await;
+
+		let result = dispatcher
+			.dispatch(&OrderShipped { order_id: 123 })
+			.await
+			.unwrap();
+
+		assert_eq!(result, ControlFlow::Stop);
+	}
+
+	#[tokio::test]
+	async fn test_forget() {
+		let mut dispatcher = Dispatcher::new();
+
+		let id = dispatcher
+			.listen(0, |_: OrderShipped| async move {
+				panic!("forgotten listener should not run");
+			})
+			.await;
+
+		dispatcher.forget(id).await;
+
+		dispatcher
+			.dispatch(&OrderShipped { order_id: 123 })
+			.await
+			.unwrap();
+	}
+
+	#[tokio::test]
+	async fn test_forget_all() {
+		let mut dispatcher = Dispatcher::new();
+
+		for _ in 0..3 {
+			dispatcher
+				.listen(0, |_: OrderShipped| async move {
+					panic!("forgotten listener should not run");
+				})
+				.await;
+		}
+
+		dispatcher.forget_all::<OrderShipped>().await;
+
+		assert!(!dispatcher.has_listeners::<OrderShipped>().await);
+	}
+
+	#[tokio::test]
+	async fn test_subscribe() {
+		let mut dispatcher = Dispatcher::new();
+
+		let (id, mut rx) = dispatcher.subscribe::<OrderShipped>(4).await;
+
+		dispatcher
+			.dispatch(&OrderShipped { order_id: 123 })
+			.await
+			.unwrap();
+
+		assert_eq!(rx.recv().await, Some(OrderShipped { order_id: 123 }));
+
+		drop(rx);
+		dispatcher.forget(id).await;
+
+		assert!(!dispatcher.has_listeners::<OrderShipped>().await);
+	}
+
+	#[tokio::test]
+	async fn test_topic_scoped_dispatch() {
+		let mut dispatcher: Dispatcher<&'static str> = Dispatcher::default();
+
+		dispatcher
+			.listen_on("europe", 0, |_: OrderShipped| async move {
+				panic!("wrong-topic listener should not run");
+			})
+			.await;
+
+		dispatcher
+			.listen_on("americas", 0, |event: OrderShipped| async move {
+				assert_eq!(event.order_id, 123);
+				ControlFlow::Continue
+			})
+			.await;
+
+		dispatcher
+			.dispatch_to(&"americas", &OrderShipped { order_id: 123 })
+			.await
+			.unwrap();
+	}
+
+	#[tokio::test]
+	async fn test_plain_dispatch_ignores_topics() {
+		let mut dispatcher: Dispatcher<&'static str> = Dispatcher::default();
+
+		dispatcher
+			.listen_on("americas", 0, |_: OrderShipped| async move {
+				panic!("topic-scoped listener should not run on a plain dispatch");
+			})
 			.await;
 
 		dispatcher
@@ -141,4 +469,82 @@ mod tests {
 			.await
 			.unwrap();
 	}
+
+	#[tokio::test]
+	async fn test_notify_coalesces_until_flush() {
+		use std::sync::atomic::AtomicUsize;
+
+		let mut dispatcher = Dispatcher::new();
+		let calls = Arc::new(AtomicUsize::new(0));
+		let last_seen = Arc::new(std::sync::Mutex::new(0));
+
+		{
+			let calls = Arc::clone(&calls);
+			let last_seen = Arc::clone(&last_seen);
+			dispatcher
+				.listen(0, move |event: OrderShipped| {
+					let calls = Arc::clone(&calls);
+					let last_seen = Arc::clone(&last_seen);
+					async move {
+						calls.fetch_add(1, Ordering::Relaxed);
+						*last_seen.lock().unwrap() = event.order_id;
+						ControlFlow::Continue
+					}
+				})
+				.await;
+		}
+
+		dispatcher.notify(OrderShipped { order_id: 1 }).await;
+		dispatcher.notify(OrderShipped { order_id: 2 }).await;
+		dispatcher.notify(OrderShipped { order_id: 3 }).await;
+
+		assert_eq!(calls.load(Ordering::Relaxed), 0);
+
+		dispatcher.flush().await;
+
+		assert_eq!(calls.load(Ordering::Relaxed), 1);
+		assert_eq!(*last_seen.lock().unwrap(), 3);
+
+		dispatcher.flush().await;
+
+		assert_eq!(calls.load(Ordering::Relaxed), 1);
+	}
+
+	#[tokio::test]
+	async fn test_flush_allows_notify_from_listener() {
+		#[derive(Debug, Clone, PartialEq)]
+		struct Done;
+		impl Event for Done {}
+
+		let cell: Arc<tokio::sync::OnceCell<Arc<Dispatcher>>> =
+			Arc::new(tokio::sync::OnceCell::new());
+
+		let mut dispatcher = Dispatcher::new();
+
+		{
+			let cell = Arc::clone(&cell);
+			dispatcher
+				.listen(0, move |_: OrderShipped| {
+					let cell = Arc::clone(&cell);
+					async move {
+						cell.get().unwrap().notify(Done).await;
+						ControlFlow::Continue
+					}
+				})
+				.await;
+		}
+
+		let dispatcher = Arc::new(dispatcher);
+		assert!(cell.set(Arc::clone(&dispatcher)).is_ok());
+
+		dispatcher.notify(OrderShipped { order_id: 1 }).await;
+
+		let flushed =
+			tokio::time::timeout(std::time::Duration::from_secs(1), dispatcher.flush()).await;
+
+		assert!(
+			flushed.is_ok(),
+			"flush() deadlocked when a listener notified a new event type"
+		);
+	}
 }
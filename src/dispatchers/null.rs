@@ -1,28 +1,64 @@
+use std::{
+	any::{Any, TypeId},
+	collections::HashMap,
+	sync::RwLock,
+};
+
 use super::sync;
-use crate::Event;
+use crate::{ControlFlow, Event, ListenerId};
 
+/// A fake dispatcher for tests.
+///
+/// Listeners can still be registered, but `dispatch` never invokes them. Instead every dispatched
+/// event is recorded (keyed by its type) so tests can assert on what code-under-test emitted, via
+/// [`Dispatcher::dispatched`], [`Dispatcher::assert_dispatched`] and
+/// [`Dispatcher::assert_nothing_dispatched`].
 pub struct Dispatcher {
 	dispatcher: sync::Dispatcher,
+	dispatched: RwLock<HashMap<TypeId, Vec<Box<dyn Any + Send + Sync>>>>,
 }
 
 impl Dispatcher {
 	#[must_use]
-	pub const fn new() -> Self {
+	pub fn new() -> Self {
 		Self {
 			dispatcher: sync::Dispatcher::new(),
+			dispatched: RwLock::new(HashMap::new()),
 		}
 	}
 
 	/// Register an event listener with the dispatcher.
 	///
+	/// The listener is bookkept (so [`Dispatcher::has_listeners`] reflects it) but is never
+	/// actually invoked, since [`Dispatcher::dispatch`] on this fake only records events.
+	///
 	/// # Errors
 	///
 	/// Returns an error if the listener lock is poisoned.
 	pub fn listen<Ev: Event + 'static>(
 		&mut self,
-		on_event: impl FnMut(&mut Ev) + 'static,
-	) -> Result<(), sync::Error> {
-		self.dispatcher.listen(on_event)
+		priority: i32,
+		on_event: impl FnMut(&mut Ev) -> ControlFlow + 'static,
+	) -> Result<ListenerId, sync::Error> {
+		self.dispatcher.listen(priority, on_event)
+	}
+
+	/// Remove a previously registered listener.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the listener lock is poisoned.
+	pub fn forget(&mut self, id: ListenerId) -> Result<(), sync::Error> {
+		self.dispatcher.forget(id)
+	}
+
+	/// Remove every listener registered for a given event type.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the listener lock is poisoned.
+	pub fn forget_all<Ev: Event + 'static>(&mut self) -> Result<(), sync::Error> {
+		self.dispatcher.forget_all::<Ev>()
 	}
 
 	/// Determine if a given event has listeners.
@@ -34,8 +70,125 @@ impl Dispatcher {
 		self.dispatcher.has_listeners::<Ev>()
 	}
 
-	/// Don't fire an event.
-	pub fn dispatch<Ev: Event + 'static>(&self, _: &mut Ev) {
-		//
+	/// Record a dispatched event. Unlike a real dispatcher, no listener is ever invoked.
+	pub fn dispatch<Ev: Event + Clone + Send + Sync + 'static>(&self, event: &mut Ev) {
+		self.dispatched
+			.write()
+			.unwrap_or_else(std::sync::PoisonError::into_inner)
+			.entry(TypeId::of::<Ev>())
+			.or_default()
+			.push(Box::new(event.clone()));
+	}
+
+	/// Every event of type `Ev` dispatched so far, in dispatch order.
+	///
+	/// # Panics
+	///
+	/// Panics if an event recorded under this type id does not actually downcast to `Ev`; this
+	/// would indicate a bug in [`Dispatcher::dispatch`], not a caller error.
+	#[must_use]
+	pub fn dispatched<Ev: Event + Clone + Send + Sync + 'static>(&self) -> Vec<Ev> {
+		self.dispatched
+			.read()
+			.unwrap_or_else(std::sync::PoisonError::into_inner)
+			.get(&TypeId::of::<Ev>())
+			.map(|events| {
+				events
+					.iter()
+					.map(|event| {
+						event
+							.downcast_ref::<Ev>()
+							.expect("event type mismatch in recording dispatcher")
+							.clone()
+					})
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+
+	/// Assert that at least one event of type `Ev` was dispatched.
+	///
+	/// # Panics
+	///
+	/// Panics if no event of type `Ev` was dispatched.
+	pub fn assert_dispatched<Ev: Event + Clone + Send + Sync + 'static>(&self) {
+		assert!(
+			!self.dispatched::<Ev>().is_empty(),
+			"expected an event of this type to have been dispatched, but none was"
+		);
+	}
+
+	/// Assert that nothing has been dispatched at all.
+	///
+	/// # Panics
+	///
+	/// Panics if any event was dispatched.
+	pub fn assert_nothing_dispatched(&self) {
+		assert!(
+			self.dispatched
+				.read()
+				.unwrap_or_else(std::sync::PoisonError::into_inner)
+				.values()
+				.all(Vec::is_empty),
+			"expected nothing to have been dispatched, but something was"
+		);
+	}
+}
+
+impl Default for Dispatcher {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[derive(Debug, Clone, PartialEq)]
+	struct OrderShipped {
+		order_id: u64,
+	}
+	impl Event for OrderShipped {}
+
+	#[test]
+	fn test_records_dispatched_events() {
+		let dispatcher = Dispatcher::new();
+
+		dispatcher.dispatch(&mut OrderShipped { order_id: 123 });
+
+		dispatcher.assert_dispatched::<OrderShipped>();
+		assert_eq!(
+			dispatcher.dispatched::<OrderShipped>(),
+			vec![OrderShipped { order_id: 123 }]
+		);
+	}
+
+	#[test]
+	fn test_assert_nothing_dispatched() {
+		let dispatcher = Dispatcher::new();
+
+		dispatcher.assert_nothing_dispatched();
+	}
+
+	#[test]
+	#[should_panic(expected = "expected an event of this type to have been dispatched")]
+	fn test_assert_dispatched_panics_when_nothing_dispatched() {
+		let dispatcher = Dispatcher::new();
+
+		dispatcher.assert_dispatched::<OrderShipped>();
+	}
+
+	#[test]
+	fn test_dispatch_never_invokes_listeners() {
+		let mut dispatcher = Dispatcher::new();
+
+		dispatcher
+			.listen(0, |_: &mut OrderShipped| {
+				panic!("the fake dispatcher must never invoke a listener");
+			})
+			.unwrap();
+
+		dispatcher.dispatch(&mut OrderShipped { order_id: 123 });
 	}
 }
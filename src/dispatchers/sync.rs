@@ -1,55 +1,131 @@
 use std::{
 	any::{Any, TypeId},
+	cmp::Reverse,
+	collections::HashMap,
+	hash::Hash,
 	sync::RwLock,
 };
 
-use crate::Event;
+use crate::{ControlFlow, Event, ListenerId};
 
 #[allow(clippy::type_complexity)]
 struct Listener {
-	event: TypeId,
-	handler: Box<dyn (FnMut(&mut dyn Any) -> Result<(), Error>)>,
+	id: ListenerId,
+	priority: i32,
+	handler: Box<dyn (FnMut(&mut dyn Any) -> Result<ControlFlow, Error>)>,
 }
 
-pub struct Dispatcher {
-	listeners: RwLock<Vec<Listener>>,
+/// An event dispatcher, optionally scoped by a `Topic` key (`()` by default, which preserves
+/// plain type-based routing).
+#[allow(clippy::type_complexity)]
+pub struct Dispatcher<Topic = ()> {
+	listeners: RwLock<HashMap<(TypeId, Option<Topic>), Vec<Listener>>>,
+	next_id: u64,
 }
 
-impl Dispatcher {
+impl Dispatcher<()> {
 	/// Create a new event dispatcher instance.
 	#[must_use]
-	pub const fn new() -> Self {
-		Self {
-			listeners: RwLock::new(Vec::new()),
-		}
+	pub fn new() -> Self {
+		Self::default()
 	}
+}
 
-	/// Register an event listener with the dispatcher.
+impl<Topic: Eq + Hash> Dispatcher<Topic> {
+	/// Register an event listener with the dispatcher, returning a [`ListenerId`] that can later
+	/// be passed to [`Dispatcher::forget`] to remove it.
+	///
+	/// Listeners are invoked in descending order of `priority` (ties keep registration order).
+	/// Returning [`ControlFlow::Stop`] from the handler halts propagation to lower-priority
+	/// listeners for that dispatch. Only [`Dispatcher::dispatch`] (not [`Dispatcher::dispatch_to`])
+	/// ever calls a listener registered this way.
 	///
 	/// # Errors
 	///
 	/// Returns an error if the listener lock is poisoned.
 	pub fn listen<Ev: Event + 'static>(
 		&mut self,
-		mut on_event: impl FnMut(&mut Ev) + 'static,
-	) -> Result<(), Error> {
+		priority: i32,
+		on_event: impl FnMut(&mut Ev) -> ControlFlow + 'static,
+	) -> Result<ListenerId, Error> {
+		self.register(None, priority, on_event)
+	}
+
+	/// Register an event listener scoped to a `topic`, returning a [`ListenerId`] that can later
+	/// be passed to [`Dispatcher::forget`] to remove it.
+	///
+	/// Only [`Dispatcher::dispatch_to`] calls with a matching `topic` reach listeners registered
+	/// this way; plain [`Dispatcher::dispatch`] never does.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the listener lock is poisoned.
+	pub fn listen_on<Ev: Event + 'static>(
+		&mut self,
+		topic: Topic,
+		priority: i32,
+		on_event: impl FnMut(&mut Ev) -> ControlFlow + 'static,
+	) -> Result<ListenerId, Error> {
+		self.register(Some(topic), priority, on_event)
+	}
+
+	fn register<Ev: Event + 'static>(
+		&mut self,
+		topic: Option<Topic>,
+		priority: i32,
+		mut on_event: impl FnMut(&mut Ev) -> ControlFlow + 'static,
+	) -> Result<ListenerId, Error> {
 		let mut listeners = self.listeners.write().map_err(|_| Error::LockPoisoned)?;
 
-		listeners.push(Listener {
-			event: TypeId::of::<Ev>(),
-			handler: Box::new(move |ev: &mut dyn Any| -> Result<(), Error> {
-				(on_event)(ev.downcast_mut().ok_or(Error::UnregisteredEvent)?);
+		let id = ListenerId(self.next_id);
+		self.next_id += 1;
 
-				Ok(())
-			}),
-		});
+		listeners
+			.entry((TypeId::of::<Ev>(), topic))
+			.or_default()
+			.push(Listener {
+				id,
+				priority,
+				handler: Box::new(move |ev: &mut dyn Any| -> Result<ControlFlow, Error> {
+					Ok((on_event)(ev.downcast_mut().ok_or(Error::UnregisteredEvent)?))
+				}),
+			});
 
 		drop(listeners);
 
+		Ok(id)
+	}
+
+	/// Remove a previously registered listener, in any topic.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the listener lock is poisoned.
+	pub fn forget(&mut self, id: ListenerId) -> Result<(), Error> {
+		self.listeners
+			.write()
+			.map_err(|_| Error::LockPoisoned)?
+			.values_mut()
+			.for_each(|bucket| bucket.retain(|listener| listener.id != id));
+
+		Ok(())
+	}
+
+	/// Remove every listener registered for a given event type, across all topics.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the listener lock is poisoned.
+	pub fn forget_all<Ev: Event + 'static>(&mut self) -> Result<(), Error> {
+		self.listeners
+			.write()
+			.map_err(|_| Error::LockPoisoned)?
+			.retain(|(event, _), _| *event != TypeId::of::<Ev>());
+
 		Ok(())
 	}
 
-	/// Determine if a given event has listeners.
+	/// Determine if a given event has listeners, in any topic.
 	///
 	/// # Errors
 	///
@@ -57,33 +133,78 @@ impl Dispatcher {
 	pub fn has_listeners<Ev: Event + 'static>(&self) -> Result<bool, Error> {
 		let listeners = self.listeners.read().map_err(|_| Error::LockPoisoned)?;
 
-		Ok(listeners.iter().any(|l| l.event == TypeId::of::<Ev>()))
+		Ok(listeners
+			.iter()
+			.any(|(key, bucket)| key.0 == TypeId::of::<Ev>() && !bucket.is_empty()))
 	}
 
-	/// Fire an event and call the listeners.
+	/// Fire an event and call the type-only listeners registered via [`Dispatcher::listen`],
+	/// highest priority first.
+	///
+	/// Returns [`ControlFlow::Stop`] if a listener halted propagation, or
+	/// [`ControlFlow::Continue`] once every matching listener has run.
 	///
 	/// # Errors
 	///
-	/// Returns an error if the event type is not registered with the dispatcher.
-	#[allow(clippy::significant_drop_in_scrutinee)]
-	pub fn dispatch<Ev: Event + 'static>(&self, event: &mut Ev) -> Result<(), Error> {
-		for listener in self
-			.listeners
-			.write()
-			.map_err(|_| Error::LockPoisoned)?
-			.iter_mut()
-			.filter(|listener| listener.event == TypeId::of::<Ev>())
-		{
-			(listener.handler)(event)?;
+	/// Returns an error if the listener lock is poisoned.
+	pub fn dispatch<Ev: Event + 'static>(&self, event: &mut Ev) -> Result<ControlFlow, Error> {
+		self.dispatch_matching(None, event)
+	}
+
+	/// Fire an event for a specific `topic`, calling only listeners registered for that
+	/// `(event, topic)` pair via [`Dispatcher::listen_on`], highest priority first.
+	///
+	/// Returns [`ControlFlow::Stop`] if a listener halted propagation, or
+	/// [`ControlFlow::Continue`] once every matching listener has run.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the listener lock is poisoned.
+	pub fn dispatch_to<Ev: Event + 'static>(
+		&self,
+		topic: &Topic,
+		event: &mut Ev,
+	) -> Result<ControlFlow, Error>
+	where
+		Topic: Clone,
+	{
+		self.dispatch_matching(Some(topic.clone()), event)
+	}
+
+	#[allow(
+		clippy::significant_drop_in_scrutinee,
+		clippy::significant_drop_tightening
+	)]
+	fn dispatch_matching<Ev: Event + 'static>(
+		&self,
+		topic: Option<Topic>,
+		event: &mut Ev,
+	) -> Result<ControlFlow, Error> {
+		let mut listeners = self.listeners.write().map_err(|_| Error::LockPoisoned)?;
+
+		let Some(bucket) = listeners.get_mut(&(TypeId::of::<Ev>(), topic)) else {
+			return Ok(ControlFlow::Continue);
+		};
+
+		let mut matching = bucket.iter_mut().collect::<Vec<_>>();
+		matching.sort_by_key(|listener| Reverse(listener.priority));
+
+		for listener in matching {
+			if (listener.handler)(event)? == ControlFlow::Stop {
+				return Ok(ControlFlow::Stop);
+			}
 		}
 
-		Ok(())
+		Ok(ControlFlow::Continue)
 	}
 }
 
-impl Default for Dispatcher {
+impl<Topic> Default for Dispatcher<Topic> {
 	fn default() -> Self {
-		Self::new()
+		Self {
+			listeners: RwLock::new(HashMap::new()),
+			next_id: 0,
+		}
 	}
 }
 
@@ -113,8 +234,126 @@ mod tests {
 		let mut dispatcher = Dispatcher::new();
 
 		dispatcher
-			.listen(|event: &mut OrderShipped| {
+			.listen(0, |event: &mut OrderShipped| {
 				assert_eq!(event.order_id, 123);
+				ControlFlow::Continue
+			})
+			.unwrap();
+
+		dispatcher
+			.dispatch(&mut OrderShipped { order_id: 123 })
+			.unwrap();
+	}
+
+	#[test]
+	fn test_priority_ordering() {
+		use std::{cell::RefCell, rc::Rc};
+
+		let mut dispatcher = Dispatcher::new();
+		let order = Rc::new(RefCell::new(Vec::new()));
+
+		for priority in [0, 10, 5] {
+			let order = Rc::clone(&order);
+			dispatcher
+				.listen(priority, move |_: &mut OrderShipped| {
+					order.borrow_mut().push(priority);
+					ControlFlow::Continue
+				})
+				.unwrap();
+		}
+
+		dispatcher
+			.dispatch(&mut OrderShipped { order_id: 123 })
+			.unwrap();
+
+		assert_eq!(*order.borrow(), vec![10, 5, 0]);
+	}
+
+	#[test]
+	fn test_halting_propagation() {
+		let mut dispatcher = Dispatcher::new();
+
+		dispatcher
+			.listen(10, |_: &mut OrderShipped| ControlFlow::Stop)
+			.unwrap();
+		dispatcher
+			.listen(0, |_: &mut OrderShipped| {
+				panic!("lower-priority listener should not run after a halt");
+			})
+			.unwrap();
+
+		let result = dispatcher
+			.dispatch(&mut OrderShipped { order_id: 123 })
+			.unwrap();
+
+		assert_eq!(result, ControlFlow::Stop);
+	}
+
+	#[test]
+	fn test_forget() {
+		let mut dispatcher = Dispatcher::new();
+
+		let id = dispatcher
+			.listen(0, |_: &mut OrderShipped| {
+				panic!("forgotten listener should not run");
+			})
+			.unwrap();
+
+		dispatcher.forget(id).unwrap();
+
+		dispatcher
+			.dispatch(&mut OrderShipped { order_id: 123 })
+			.unwrap();
+	}
+
+	#[test]
+	fn test_forget_all() {
+		let mut dispatcher = Dispatcher::new();
+
+		for _ in 0..3 {
+			dispatcher
+				.listen(0, |_: &mut OrderShipped| {
+					panic!("forgotten listener should not run");
+				})
+				.unwrap();
+		}
+
+		dispatcher.forget_all::<OrderShipped>().unwrap();
+
+		assert!(!dispatcher.has_listeners::<OrderShipped>().unwrap());
+	}
+
+	#[test]
+	fn test_topic_scoped_dispatch() {
+		let mut dispatcher: Dispatcher<&'static str> = Dispatcher::default();
+
+		dispatcher
+			.listen_on("europe", 0, |_: &mut OrderShipped| {
+				panic!("wrong-topic listener should not run");
+			})
+			.unwrap();
+
+		let id = dispatcher
+			.listen_on("americas", 0, |event: &mut OrderShipped| {
+				assert_eq!(event.order_id, 123);
+				ControlFlow::Continue
+			})
+			.unwrap();
+
+		dispatcher
+			.dispatch_to(&"americas", &mut OrderShipped { order_id: 123 })
+			.unwrap();
+
+		dispatcher.forget(id).unwrap();
+	}
+
+	#[test]
+	fn test_plain_dispatch_ignores_topics() {
+		let mut dispatcher: Dispatcher<&'static str> = Dispatcher::default();
+
+		dispatcher
+			.listen_on("americas", 0, |_: &mut OrderShipped| {
+				panic!("topic-scoped listener should not run on a plain dispatch");
 			})
 			.unwrap();
 
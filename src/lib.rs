@@ -5,7 +5,7 @@
 //! # Usage
 //!
 //! ```rust
-//! use epicenter::{Event, AsyncDispatcher};
+//! use epicenter::{Event, AsyncDispatcher, ControlFlow};
 //!
 //! # #[derive(Debug, Clone)]
 //! struct OrderShipped {
@@ -17,8 +17,9 @@
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! let mut dispatcher = AsyncDispatcher::new();
 //!
-//! dispatcher.listen(|event: OrderShipped| async move {
+//! dispatcher.listen(0, |event: OrderShipped| async move {
 //!     assert_eq!(event.order_id, 123);
+//!     ControlFlow::Continue
 //! }).await;
 //!
 //! dispatcher.dispatch(&mut OrderShipped {
@@ -35,3 +36,17 @@ pub use dispatchers::r#async::Dispatcher as AsyncDispatcher;
 pub use dispatchers::sync::Dispatcher as SyncDispatcher;
 
 pub trait Event {}
+
+/// Controls whether a dispatcher keeps invoking lower-priority listeners after a listener runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlFlow {
+	/// Continue invoking the remaining listeners.
+	Continue,
+	/// Stop invoking any further listeners for this dispatch.
+	Stop,
+}
+
+/// A unique handle to a registered listener, returned by `listen` and accepted by `forget` to
+/// unregister it again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ListenerId(u64);